@@ -0,0 +1,235 @@
+//! gRPC transport for the same five benchmark tools exposed over MCP in `main.rs`. Shares
+//! the core logic in `tools` so a workload behaves identically regardless of which transport
+//! carried the request.
+
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures::Stream;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::{transport::Server, Request, Response, Status};
+
+use crate::metrics::ToolMetrics;
+use crate::tools;
+
+pub mod benchmark {
+    tonic::include_proto!("benchmark");
+}
+
+use benchmark::{
+    batch_fetch_progress,
+    benchmark_server::{Benchmark, BenchmarkServer as BenchmarkGrpcServer},
+    db_query_progress, BatchFetchProgress, BatchFetchRequest, BatchSummary, DbQueryProgress,
+    DbQueryRequest, DbQueryResult, FetchOutcome, FetchRequest, FetchResponse, FibonacciRequest,
+    FibonacciResponse, ProcessJsonRequest, ProcessJsonResponse,
+};
+
+struct BenchmarkGrpc {
+    http_client: reqwest::Client,
+    compute_pool: Arc<rayon::ThreadPool>,
+    metrics: Arc<ToolMetrics>,
+}
+
+type ProgressStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Benchmark for BenchmarkGrpc {
+    async fn calculate_fibonacci(
+        &self,
+        request: Request<FibonacciRequest>,
+    ) -> Result<Response<FibonacciResponse>, Status> {
+        self.metrics
+            .track_grpc("calculate_fibonacci", async {
+                let req = request.into_inner();
+                let (result, pool_size) =
+                    tools::run_fibonacci(&self.compute_pool, req.n, req.iterative)
+                        .await
+                        .map_err(Status::invalid_argument)?;
+                Ok(Response::new(FibonacciResponse {
+                    input: req.n,
+                    result,
+                    mode: if req.iterative {
+                        "iterative"
+                    } else {
+                        "recursive"
+                    }
+                    .to_string(),
+                    pool_size: pool_size as u32,
+                }))
+            })
+            .await
+    }
+
+    async fn process_json_data(
+        &self,
+        request: Request<ProcessJsonRequest>,
+    ) -> Result<Response<ProcessJsonResponse>, Status> {
+        self.metrics
+            .track_grpc("process_json_data", async {
+                let req = request.into_inner();
+                let data: serde_json::Value = serde_json::from_str(&req.data_json)
+                    .map_err(|e| Status::invalid_argument(format!("invalid data_json: {e}")))?;
+                let (original_keys, transformed) = tools::run_process_json(&data);
+                Ok(Response::new(ProcessJsonResponse {
+                    original_keys,
+                    transformed_json: transformed.to_string(),
+                }))
+            })
+            .await
+    }
+
+    async fn fetch_external_data(
+        &self,
+        request: Request<FetchRequest>,
+    ) -> Result<Response<FetchResponse>, Status> {
+        self.metrics
+            .track_grpc("fetch_external_data", async {
+                let req = request.into_inner();
+                let (status, elapsed) = tools::run_fetch(&self.http_client, &req.endpoint)
+                    .await
+                    .map_err(Status::unavailable)?;
+                Ok(Response::new(FetchResponse {
+                    url: req.endpoint,
+                    status_code: status as u32,
+                    response_time_ms: elapsed as u64,
+                }))
+            })
+            .await
+    }
+
+    type SimulateDatabaseQueryStream = ProgressStream<DbQueryProgress>;
+
+    async fn simulate_database_query(
+        &self,
+        request: Request<DbQueryRequest>,
+    ) -> Result<Response<Self::SimulateDatabaseQueryStream>, Status> {
+        let req = request.into_inner();
+        let delay_ms = req.delay_ms;
+        let (tick_tx, mut tick_rx) = mpsc::unbounded_channel::<f64>();
+        let (out_tx, out_rx) = mpsc::unbounded_channel();
+
+        let forward_tx = out_tx.clone();
+        tokio::spawn(async move {
+            while let Some(progress) = tick_rx.recv().await {
+                let _ = forward_tx.send(Ok(DbQueryProgress {
+                    event: Some(db_query_progress::Event::ProgressPercent(progress)),
+                }));
+            }
+        });
+
+        // Run the query in its own task and return the stream immediately, so ticks reach
+        // the client as they happen instead of all being buffered until the query completes.
+        let metrics = self.metrics.clone();
+        tokio::spawn(async move {
+            let outcome = metrics
+                .track_grpc("simulate_database_query", async {
+                    tools::run_db_query(req.query, delay_ms, Some(tick_tx))
+                        .await
+                        .map_err(Status::invalid_argument)
+                })
+                .await;
+
+            let message = match outcome {
+                Ok((query, timestamp)) => Ok(DbQueryProgress {
+                    event: Some(db_query_progress::Event::Result(DbQueryResult {
+                        query,
+                        delay_ms,
+                        timestamp,
+                    })),
+                }),
+                Err(e) => Err(e),
+            };
+            let _ = out_tx.send(message);
+        });
+
+        Ok(Response::new(
+            Box::pin(UnboundedReceiverStream::new(out_rx)) as Self::SimulateDatabaseQueryStream,
+        ))
+    }
+
+    type FetchExternalDataBatchStream = ProgressStream<BatchFetchProgress>;
+
+    async fn fetch_external_data_batch(
+        &self,
+        request: Request<BatchFetchRequest>,
+    ) -> Result<Response<Self::FetchExternalDataBatchStream>, Status> {
+        let req = request.into_inner();
+        let (item_tx, mut item_rx) = mpsc::unbounded_channel::<serde_json::Value>();
+        let (out_tx, out_rx) = mpsc::unbounded_channel();
+
+        let forward_tx = out_tx.clone();
+        tokio::spawn(async move {
+            while let Some(result) = item_rx.recv().await {
+                let _ = forward_tx.send(Ok(BatchFetchProgress {
+                    event: Some(batch_fetch_progress::Event::Outcome(FetchOutcome {
+                        url: result["url"].as_str().unwrap_or_default().to_string(),
+                        status_code: result["status_code"].as_u64().unwrap_or(0) as u32,
+                        timed_out: result["timed_out"].as_bool().unwrap_or(false),
+                        error: result["error"].as_str().unwrap_or_default().to_string(),
+                        response_time_ms: result["response_time_ms"].as_u64().unwrap_or(0),
+                    })),
+                }));
+            }
+        });
+
+        // Run the batch in its own task and return the stream immediately, so per-URL
+        // outcomes reach the client as they land instead of all being buffered until the
+        // whole batch completes.
+        let metrics = self.metrics.clone();
+        let client = self.http_client.clone();
+        tokio::spawn(async move {
+            let outcome = metrics
+                .track_grpc("fetch_external_data_batch", async {
+                    tools::run_batch_fetch(
+                        client,
+                        req.endpoints,
+                        req.max_concurrency as usize,
+                        req.timeout_ms,
+                        Some(item_tx),
+                    )
+                    .await
+                    .map_err(Status::invalid_argument)
+                })
+                .await;
+
+            let message = match outcome {
+                Ok(response) => {
+                    let summary = &response["summary"];
+                    Ok(BatchFetchProgress {
+                        event: Some(batch_fetch_progress::Event::Summary(BatchSummary {
+                            total_requests: summary["total_requests"].as_u64().unwrap_or(0) as u32,
+                            successes: summary["successes"].as_u64().unwrap_or(0) as u32,
+                            failures: summary["failures"].as_u64().unwrap_or(0) as u32,
+                            elapsed_ms: summary["elapsed_ms"].as_u64().unwrap_or(0),
+                        })),
+                    })
+                }
+                Err(e) => Err(e),
+            };
+            let _ = out_tx.send(message);
+        });
+
+        Ok(Response::new(
+            Box::pin(UnboundedReceiverStream::new(out_rx)) as Self::FetchExternalDataBatchStream,
+        ))
+    }
+}
+
+pub async fn serve(
+    addr: SocketAddr,
+    http_client: reqwest::Client,
+    compute_pool: Arc<rayon::ThreadPool>,
+    metrics: Arc<ToolMetrics>,
+) -> Result<(), tonic::transport::Error> {
+    let grpc = BenchmarkGrpc {
+        http_client,
+        compute_pool,
+        metrics,
+    };
+    Server::builder()
+        .add_service(BenchmarkGrpcServer::new(grpc))
+        .serve(addr)
+        .await
+}
@@ -1,21 +1,31 @@
+mod grpc;
+mod metrics;
+mod tools;
+
 use axum::{routing::get, Json, Router};
 use rmcp::{
-    ErrorData as McpError, ServerHandler,
     handler::server::router::tool::ToolRouter,
     handler::server::wrapper::Parameters,
     model::*,
-    schemars, tool, tool_handler, tool_router,
+    schemars,
+    service::{RequestContext, RoleServer},
+    tool, tool_handler, tool_router,
     transport::streamable_http_server::{
         session::local::LocalSessionManager, StreamableHttpService,
     },
+    ErrorData as McpError, ServerHandler,
 };
 use serde_json::Value;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 struct FibonacciParams {
-    /// The position in the Fibonacci sequence (0-40)
+    /// The position in the Fibonacci sequence (0-40 recursive, 0-90 iterative)
     n: i64,
+    /// Use the O(n) iterative algorithm instead of the naive recursive one
+    #[serde(default)]
+    iterative: bool,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -24,6 +34,16 @@ struct FetchParams {
     endpoint: String,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct BatchFetchParams {
+    /// URLs to fetch data from, fanned out concurrently
+    endpoints: Vec<String>,
+    /// Maximum number of requests allowed in flight at once
+    max_concurrency: usize,
+    /// Per-request timeout in milliseconds
+    timeout_ms: u64,
+}
+
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 struct ProcessJsonParams {
     /// JSON data to process
@@ -38,68 +58,176 @@ struct DbQueryParams {
     delay_ms: u64,
 }
 
-fn fibonacci(n: u32) -> u64 {
-    if n <= 1 {
-        return n as u64;
+#[derive(Debug, Clone, serde::Deserialize)]
+struct TlsConfig {
+    cert_path: String,
+    key_path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Transport {
+    Http,
+    Grpc,
+    Quic,
+}
+
+impl std::str::FromStr for Transport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "http" => Ok(Transport::Http),
+            "grpc" => Ok(Transport::Grpc),
+            "quic" => Ok(Transport::Quic),
+            other => Err(format!("unknown transport: {other}")),
+        }
     }
-    fibonacci(n - 1) + fibonacci(n - 2)
 }
 
-fn uppercase_values(value: &Value) -> Value {
-    match value {
-        Value::String(s) => Value::String(s.to_uppercase()),
-        Value::Object(map) => {
-            let new_map: serde_json::Map<String, Value> = map
-                .iter()
-                .map(|(k, v)| (k.clone(), uppercase_values(v)))
-                .collect();
-            Value::Object(new_map)
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+struct ServerConfig {
+    bind_addr: String,
+    request_timeout_ms: u64,
+    connect_timeout_ms: u64,
+    client_id: String,
+    enable_tracing: bool,
+    tls: Option<TlsConfig>,
+    transport: Transport,
+    /// Where `/health` and `/metrics` are served when `transport` is `grpc` (the `http`
+    /// transport serves them alongside `/mcp` on `bind_addr` instead; `quic` exits at startup).
+    admin_bind_addr: String,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_addr: "0.0.0.0:8084".to_string(),
+            request_timeout_ms: 10_000,
+            connect_timeout_ms: 5_000,
+            client_id: "mcp-rust-server".to_string(),
+            enable_tracing: true,
+            tls: None,
+            transport: Transport::Http,
+            admin_bind_addr: "0.0.0.0:8085".to_string(),
         }
-        Value::Array(arr) => Value::Array(arr.iter().map(uppercase_values).collect()),
-        other => other.clone(),
     }
 }
 
+impl ServerConfig {
+    /// Loads from the file at `SERVER_CONFIG_FILE` (TOML) if set, falling back to individual
+    /// `SERVER_*` environment variables, and finally to the defaults above.
+    fn load() -> Self {
+        if let Ok(path) = std::env::var("SERVER_CONFIG_FILE") {
+            let contents =
+                std::fs::read_to_string(&path).expect("Failed to read SERVER_CONFIG_FILE");
+            return toml::from_str(&contents).expect("Failed to parse SERVER_CONFIG_FILE");
+        }
+
+        let mut config = Self::default();
+        if let Ok(v) = std::env::var("SERVER_BIND_ADDR") {
+            config.bind_addr = v;
+        }
+        if let Some(v) = std::env::var("SERVER_REQUEST_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.request_timeout_ms = v;
+        }
+        if let Some(v) = std::env::var("SERVER_CONNECT_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.connect_timeout_ms = v;
+        }
+        if let Ok(v) = std::env::var("SERVER_CLIENT_ID") {
+            config.client_id = v;
+        }
+        if let Ok(v) = std::env::var("SERVER_ENABLE_TRACING") {
+            config.enable_tracing = v.parse().unwrap_or(true);
+        }
+        if let (Ok(cert_path), Ok(key_path)) = (
+            std::env::var("SERVER_TLS_CERT_PATH"),
+            std::env::var("SERVER_TLS_KEY_PATH"),
+        ) {
+            config.tls = Some(TlsConfig {
+                cert_path,
+                key_path,
+            });
+        }
+        if let Some(v) = std::env::var("SERVER_TRANSPORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.transport = v;
+        }
+        if let Ok(v) = std::env::var("SERVER_ADMIN_BIND_ADDR") {
+            config.admin_bind_addr = v;
+        }
+        config
+    }
+}
+
+const COMPUTE_POOL_SIZE_ENV: &str = "COMPUTE_POOL_SIZE";
+
+fn compute_pool_size() -> usize {
+    std::env::var(COMPUTE_POOL_SIZE_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or_else(num_cpus::get)
+}
+
 #[derive(Clone)]
 struct BenchmarkServer {
     tool_router: ToolRouter<Self>,
     http_client: reqwest::Client,
+    compute_pool: Arc<rayon::ThreadPool>,
+    metrics: Arc<metrics::ToolMetrics>,
 }
 
 #[tool_router]
 impl BenchmarkServer {
-    fn new(http_client: reqwest::Client) -> Self {
+    fn new(
+        http_client: reqwest::Client,
+        compute_pool: Arc<rayon::ThreadPool>,
+        metrics: Arc<metrics::ToolMetrics>,
+    ) -> Self {
         Self {
             tool_router: Self::tool_router(),
             http_client,
+            compute_pool,
+            metrics,
         }
     }
 
-    #[tool(description = "Calculate a Fibonacci number recursively (CPU-bound)")]
-    fn calculate_fibonacci(
+    #[tool(
+        description = "Calculate a Fibonacci number on a bounded compute pool (recursive for CPU stress, iterative for larger n)"
+    )]
+    async fn calculate_fibonacci(
         &self,
         Parameters(params): Parameters<FibonacciParams>,
     ) -> Result<CallToolResult, McpError> {
-        if params.n < 0 {
-            return Ok(CallToolResult::error(vec![Content::text(
-                "n must be between 0 and 40",
-            )]));
-        }
-        if params.n > 40 {
-            return Ok(CallToolResult::error(vec![Content::text(
-                "n must be between 0 and 40",
-            )]));
-        }
-        let n = params.n as u32;
-        let result = fibonacci(n);
-        let response = serde_json::json!({
-            "input": params.n,
-            "result": result,
-            "server_type": "rust"
-        });
-        Ok(CallToolResult::success(vec![Content::text(
-            response.to_string(),
-        )]))
+        self.metrics
+            .track("calculate_fibonacci", async {
+                match tools::run_fibonacci(&self.compute_pool, params.n, params.iterative).await {
+                    Ok((result, pool_size)) => {
+                        let response = serde_json::json!({
+                            "input": params.n,
+                            "result": result,
+                            "mode": if params.iterative { "iterative" } else { "recursive" },
+                            "pool_size": pool_size,
+                            "server_type": "rust"
+                        });
+                        Ok(CallToolResult::success(vec![Content::text(
+                            response.to_string(),
+                        )]))
+                    }
+                    Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+                }
+            })
+            .await
     }
 
     #[tool(description = "Fetch data from an external HTTP endpoint")]
@@ -107,70 +235,164 @@ impl BenchmarkServer {
         &self,
         Parameters(params): Parameters<FetchParams>,
     ) -> Result<CallToolResult, McpError> {
-        let start = Instant::now();
-        match self.http_client.get(&params.endpoint).send().await {
-            Ok(resp) => {
-                let status = resp.status().as_u16();
-                let elapsed = start.elapsed().as_millis();
-                let response = serde_json::json!({
-                    "url": params.endpoint,
-                    "status_code": status,
-                    "response_time_ms": elapsed,
-                    "server_type": "rust"
-                });
-                Ok(CallToolResult::success(vec![Content::text(
-                    response.to_string(),
-                )]))
-            }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Request failed: {}",
-                e
-            ))])),
-        }
+        self.metrics
+            .track("fetch_external_data", async {
+                match tools::run_fetch(&self.http_client, &params.endpoint).await {
+                    Ok((status, elapsed)) => {
+                        let response = serde_json::json!({
+                            "url": params.endpoint,
+                            "status_code": status,
+                            "response_time_ms": elapsed,
+                            "server_type": "rust"
+                        });
+                        Ok(CallToolResult::success(vec![Content::text(
+                            response.to_string(),
+                        )]))
+                    }
+                    Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                        "Request failed: {}",
+                        e
+                    ))])),
+                }
+            })
+            .await
+    }
+
+    #[tool(
+        description = "Fetch data from multiple HTTP endpoints concurrently, bounded by max_concurrency and a per-request timeout"
+    )]
+    async fn fetch_external_data_batch(
+        &self,
+        Parameters(params): Parameters<BatchFetchParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        self.metrics
+            .track("fetch_external_data_batch", async {
+                let progress_token = context.meta.get_progress_token();
+                let total_requests = params.endpoints.len();
+                // One progress notification per completed URL, so a fanned-out batch still
+                // gives clients live feedback instead of going quiet until the whole set
+                // finishes.
+                let (progress_tx, notify_task) = match progress_token {
+                    Some(token) => {
+                        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+                        let peer = context.peer.clone();
+                        let task = tokio::spawn(async move {
+                            let mut completed = 0f64;
+                            while rx.recv().await.is_some() {
+                                completed += 1.0;
+                                let _ = peer
+                                    .notify_progress(ProgressNotificationParam {
+                                        progress_token: token.clone(),
+                                        progress: completed,
+                                        total: Some(total_requests as f64),
+                                        message: None,
+                                    })
+                                    .await;
+                            }
+                        });
+                        (Some(tx), Some(task))
+                    }
+                    None => (None, None),
+                };
+
+                let outcome = tools::run_batch_fetch(
+                    self.http_client.clone(),
+                    params.endpoints,
+                    params.max_concurrency,
+                    params.timeout_ms,
+                    progress_tx,
+                )
+                .await;
+                if let Some(task) = notify_task {
+                    let _ = task.await;
+                }
+
+                match outcome {
+                    Ok(response) => Ok(CallToolResult::success(vec![Content::text(
+                        response.to_string(),
+                    )])),
+                    Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+                }
+            })
+            .await
     }
 
     #[tool(description = "Process JSON data by uppercasing all string values")]
-    fn process_json_data(
+    async fn process_json_data(
         &self,
         Parameters(params): Parameters<ProcessJsonParams>,
     ) -> Result<CallToolResult, McpError> {
-        let original_keys: Vec<String> = if let Value::Object(map) = &params.data {
-            map.keys().cloned().collect()
-        } else {
-            vec![]
-        };
-        let transformed = uppercase_values(&params.data);
-        let response = serde_json::json!({
-            "original_keys": original_keys,
-            "transformed_data": transformed,
-            "server_type": "rust"
-        });
-        Ok(CallToolResult::success(vec![Content::text(
-            response.to_string(),
-        )]))
+        self.metrics
+            .track("process_json_data", async {
+                let (original_keys, transformed) = tools::run_process_json(&params.data);
+                let response = serde_json::json!({
+                    "original_keys": original_keys,
+                    "transformed_data": transformed,
+                    "server_type": "rust"
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    response.to_string(),
+                )]))
+            })
+            .await
     }
 
     #[tool(description = "Simulate a database query with configurable delay")]
     async fn simulate_database_query(
         &self,
         Parameters(params): Parameters<DbQueryParams>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
-        if params.delay_ms > 5000 {
-            return Ok(CallToolResult::error(vec![Content::text(
-                "delay_ms must be between 0 and 5000",
-            )]));
-        }
-        tokio::time::sleep(std::time::Duration::from_millis(params.delay_ms)).await;
-        let timestamp = chrono::Utc::now().to_rfc3339();
-        let response = serde_json::json!({
-            "query": params.query,
-            "delay_ms": params.delay_ms,
-            "timestamp": timestamp,
-            "server_type": "rust"
-        });
-        Ok(CallToolResult::success(vec![Content::text(
-            response.to_string(),
-        )]))
+        self.metrics
+            .track("simulate_database_query", async {
+                // Tick progress over the SSE connection for clients that attached a progress
+                // token, rather than leaving them blind for the whole delay. Mirrors the batch
+                // fetch path: the notify task is joined before returning so the final tick
+                // can't race the response.
+                let (progress_tx, notify_task) = match context.meta.get_progress_token() {
+                    Some(token) => {
+                        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+                        let peer = context.peer.clone();
+                        let task = tokio::spawn(async move {
+                            while let Some(progress) = rx.recv().await {
+                                let _ = peer
+                                    .notify_progress(ProgressNotificationParam {
+                                        progress_token: token.clone(),
+                                        progress,
+                                        total: Some(100.0),
+                                        message: None,
+                                    })
+                                    .await;
+                            }
+                        });
+                        (Some(tx), Some(task))
+                    }
+                    None => (None, None),
+                };
+
+                let outcome =
+                    tools::run_db_query(params.query.clone(), params.delay_ms, progress_tx).await;
+                if let Some(task) = notify_task {
+                    let _ = task.await;
+                }
+
+                match outcome {
+                    Ok((query, timestamp)) => {
+                        let response = serde_json::json!({
+                            "query": query,
+                            "delay_ms": params.delay_ms,
+                            "timestamp": timestamp,
+                            "server_type": "rust"
+                        });
+                        Ok(CallToolResult::success(vec![Content::text(
+                            response.to_string(),
+                        )]))
+                    }
+                    Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+                }
+            })
+            .await
     }
 }
 
@@ -197,28 +419,120 @@ async fn health() -> Json<Value> {
     }))
 }
 
+/// Serves `/health` and `/metrics` on `addr` for the gRPC transport, which doesn't otherwise
+/// have an HTTP router of its own.
+async fn serve_admin(addr: std::net::SocketAddr, metrics: Arc<metrics::ToolMetrics>) {
+    let app = Router::new().route("/health", get(health)).route(
+        "/metrics",
+        get(move || {
+            let metrics = metrics.clone();
+            async move { metrics.render() }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind admin_bind_addr");
+    tracing::info!(%addr, "Admin health/metrics endpoint listening");
+    axum::serve(listener, app)
+        .await
+        .expect("Admin server failed");
+}
+
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
+    let config = ServerConfig::load();
+    if config.enable_tracing {
+        tracing_subscriber::fmt::init();
+    }
 
     let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
+        .timeout(Duration::from_millis(config.request_timeout_ms))
+        .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
+        .user_agent(config.client_id.clone())
         .build()
         .unwrap();
-    let service = StreamableHttpService::new(
-        move || Ok(BenchmarkServer::new(client.clone())),
-        LocalSessionManager::default().into(),
-        Default::default(),
+    let pool_size = compute_pool_size();
+    let compute_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(pool_size)
+            .build()
+            .expect("Failed to build compute pool"),
     );
+    tracing::info!(pool_size, "Fibonacci compute pool ready");
+    let metrics = Arc::new(metrics::ToolMetrics::new());
 
-    let app = Router::new()
-        .route("/health", get(health))
-        .nest_service("/mcp", service);
+    let addr: std::net::SocketAddr = config
+        .bind_addr
+        .parse()
+        .expect("Invalid bind_addr in server config");
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8084")
-        .await
-        .expect("Failed to bind to port 8084");
+    match config.transport {
+        Transport::Http => {
+            let metrics_for_route = metrics.clone();
+            let service = StreamableHttpService::new(
+                move || {
+                    Ok(BenchmarkServer::new(
+                        client.clone(),
+                        compute_pool.clone(),
+                        metrics.clone(),
+                    ))
+                },
+                LocalSessionManager::default().into(),
+                Default::default(),
+            );
+            let app = Router::new()
+                .route("/health", get(health))
+                .route(
+                    "/metrics",
+                    get(move || {
+                        let metrics = metrics_for_route.clone();
+                        async move { metrics.render() }
+                    }),
+                )
+                .nest_service("/mcp", service);
+
+            match &config.tls {
+                Some(tls) => {
+                    let rustls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(
+                        &tls.cert_path,
+                        &tls.key_path,
+                    )
+                    .await
+                    .expect("Failed to load TLS certificate/key");
+                    tracing::info!(%addr, "Rust MCP server listening over HTTPS");
+                    axum_server::bind_rustls(addr, rustls_config)
+                        .serve(app.into_make_service())
+                        .await
+                        .expect("Server failed");
+                }
+                None => {
+                    let listener = tokio::net::TcpListener::bind(addr)
+                        .await
+                        .expect("Failed to bind to configured address");
+                    tracing::info!(%addr, "Rust MCP server listening");
+                    axum::serve(listener, app).await.expect("Server failed");
+                }
+            }
+        }
+        Transport::Grpc => {
+            let admin_addr: std::net::SocketAddr = config
+                .admin_bind_addr
+                .parse()
+                .expect("Invalid admin_bind_addr in server config");
+            tokio::spawn(serve_admin(admin_addr, metrics.clone()));
 
-    tracing::info!("Rust MCP server listening on port 8084");
-    axum::serve(listener, app).await.expect("Server failed");
+            tracing::info!(%addr, "Rust gRPC benchmark server listening");
+            grpc::serve(addr, client, compute_pool, metrics)
+                .await
+                .expect("gRPC server failed");
+        }
+        Transport::Quic => {
+            // Scaffolded per the cross-transport benchmark plan, but not wired up yet: a
+            // QUIC transport needs its own bidirectional framing (quic-rpc-style) rather than
+            // reusing the gRPC unary/streaming split, so it's tracked separately. `quic` is a
+            // valid config/env value, so fail the startup cleanly instead of panicking.
+            tracing::error!("QUIC transport is not implemented yet; use \"http\" or \"grpc\"");
+            std::process::exit(1);
+        }
+    }
 }
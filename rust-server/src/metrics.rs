@@ -0,0 +1,132 @@
+//! Per-tool latency/invocation/error metrics, exported in Prometheus text-exposition format
+//! on `/metrics` so benchmark runs can be scraped and aggregated externally instead of parsing
+//! `response_time_ms` out of each tool's JSON payload. Shared across transports: MCP handlers
+//! go through [`ToolMetrics::track`], gRPC handlers through [`ToolMetrics::track_grpc`].
+
+use std::future::Future;
+use std::time::Instant;
+
+use prometheus::{Encoder, HistogramVec, IntCounterVec, IntGauge, Registry, TextEncoder};
+use rmcp::{model::CallToolResult, ErrorData as McpError};
+use tonic::Status;
+
+pub struct ToolMetrics {
+    registry: Registry,
+    latency_seconds: HistogramVec,
+    invocations_total: IntCounterVec,
+    errors_total: IntCounterVec,
+    in_flight: IntGauge,
+}
+
+impl ToolMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let latency_seconds = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "tool_latency_seconds",
+                "Tool handler execution latency in seconds",
+            ),
+            &["tool"],
+        )
+        .expect("failed to create tool_latency_seconds histogram");
+
+        let invocations_total = IntCounterVec::new(
+            prometheus::Opts::new("tool_invocations_total", "Total tool invocations"),
+            &["tool"],
+        )
+        .expect("failed to create tool_invocations_total counter");
+
+        let errors_total = IntCounterVec::new(
+            prometheus::Opts::new("tool_errors_total", "Total tool invocations that errored"),
+            &["tool"],
+        )
+        .expect("failed to create tool_errors_total counter");
+
+        let in_flight = IntGauge::new(
+            "tool_requests_in_flight",
+            "Number of tool invocations currently executing",
+        )
+        .expect("failed to create tool_requests_in_flight gauge");
+
+        registry
+            .register(Box::new(latency_seconds.clone()))
+            .expect("failed to register tool_latency_seconds");
+        registry
+            .register(Box::new(invocations_total.clone()))
+            .expect("failed to register tool_invocations_total");
+        registry
+            .register(Box::new(errors_total.clone()))
+            .expect("failed to register tool_errors_total");
+        registry
+            .register(Box::new(in_flight.clone()))
+            .expect("failed to register tool_requests_in_flight");
+
+        Self {
+            registry,
+            latency_seconds,
+            invocations_total,
+            errors_total,
+            in_flight,
+        }
+    }
+
+    /// Records invocation count, latency, and error count for a tool handler body, however its
+    /// transport reports success/failure (`is_error` determines that from the raw output).
+    async fn measure<F, T>(&self, tool: &str, fut: F, is_error: impl FnOnce(&T) -> bool) -> T
+    where
+        F: Future<Output = T>,
+    {
+        self.invocations_total.with_label_values(&[tool]).inc();
+        self.in_flight.inc();
+        let start = Instant::now();
+        let result = fut.await;
+        self.in_flight.dec();
+        self.latency_seconds
+            .with_label_values(&[tool])
+            .observe(start.elapsed().as_secs_f64());
+
+        if is_error(&result) {
+            self.errors_total.with_label_values(&[tool]).inc();
+        }
+        result
+    }
+
+    /// Wraps a `#[tool]` handler body, treating either `Err(McpError)` or a `CallToolResult`
+    /// with `is_error` set as a failure.
+    pub async fn track<F>(&self, tool: &str, fut: F) -> Result<CallToolResult, McpError>
+    where
+        F: Future<Output = Result<CallToolResult, McpError>>,
+    {
+        self.measure(tool, fut, |result| match result {
+            Err(_) => true,
+            Ok(call_result) => call_result.is_error.unwrap_or(false),
+        })
+        .await
+    }
+
+    /// Wraps a gRPC handler body, treating `Err(Status)` as a failure. Used instead of
+    /// [`Self::track`] so the same metrics apply regardless of which transport carried the
+    /// request.
+    pub async fn track_grpc<F, T>(&self, tool: &str, fut: F) -> Result<T, Status>
+    where
+        F: Future<Output = Result<T, Status>>,
+    {
+        self.measure(tool, fut, Result::is_err).await
+    }
+
+    pub fn render(&self) -> String {
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&metric_families, &mut buffer)
+            .expect("failed to encode metrics");
+        String::from_utf8(buffer).expect("prometheus text encoding is always valid UTF-8")
+    }
+}
+
+impl Default for ToolMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
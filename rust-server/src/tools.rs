@@ -0,0 +1,271 @@
+//! Core tool logic shared across transports (MCP-over-HTTP today, gRPC alongside it).
+//!
+//! Each `run_*` function is transport-agnostic: it takes plain inputs, returns plain values
+//! (a `Result<_, String>` where the call can fail), and reports progress (where applicable) over an `mpsc` channel rather
+//! than a transport-specific notification type. Callers translate that into whatever their
+//! transport needs (an MCP progress notification, a gRPC stream item, ...).
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Semaphore};
+
+pub const RECURSIVE_FIBONACCI_MAX: i64 = 40;
+pub const ITERATIVE_FIBONACCI_MAX: i64 = 90;
+
+fn fibonacci(n: u32) -> u64 {
+    if n <= 1 {
+        return n as u64;
+    }
+    fibonacci(n - 1) + fibonacci(n - 2)
+}
+
+fn fibonacci_iterative(n: u32) -> u64 {
+    let (mut a, mut b) = (0u64, 1u64);
+    for _ in 0..n {
+        let next = a + b;
+        a = b;
+        b = next;
+    }
+    a
+}
+
+fn uppercase_values(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(s.to_uppercase()),
+        Value::Object(map) => {
+            let new_map: serde_json::Map<String, Value> = map
+                .iter()
+                .map(|(k, v)| (k.clone(), uppercase_values(v)))
+                .collect();
+            Value::Object(new_map)
+        }
+        Value::Array(arr) => Value::Array(arr.iter().map(uppercase_values).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Runs `n` on the compute pool, recursively or iteratively. Returns the result plus the
+/// pool's thread count, for reporting back a reproducible execution mode.
+pub async fn run_fibonacci(
+    pool: &rayon::ThreadPool,
+    n: i64,
+    iterative: bool,
+) -> Result<(u64, usize), String> {
+    let max_n = if iterative {
+        ITERATIVE_FIBONACCI_MAX
+    } else {
+        RECURSIVE_FIBONACCI_MAX
+    };
+    if n < 0 || n > max_n {
+        return Err(format!("n must be between 0 and {max_n}"));
+    }
+    let n = n as u32;
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    pool.spawn(move || {
+        let result = if iterative {
+            fibonacci_iterative(n)
+        } else {
+            fibonacci(n)
+        };
+        let _ = tx.send(result);
+    });
+    let result = rx.await.map_err(|e| e.to_string())?;
+    Ok((result, pool.current_num_threads()))
+}
+
+pub async fn run_fetch(client: &reqwest::Client, endpoint: &str) -> Result<(u16, u128), String> {
+    let start = Instant::now();
+    client
+        .get(endpoint)
+        .send()
+        .await
+        .map(|resp| (resp.status().as_u16(), start.elapsed().as_millis()))
+        .map_err(|e| e.to_string())
+}
+
+pub fn run_process_json(data: &Value) -> (Vec<String>, Value) {
+    let original_keys = if let Value::Object(map) = data {
+        map.keys().cloned().collect()
+    } else {
+        vec![]
+    };
+    (original_keys, uppercase_values(data))
+}
+
+/// Sleeps for `delay_ms`, ticking `progress` (0-100) roughly every 100ms if a sender is given.
+pub async fn run_db_query(
+    query: String,
+    delay_ms: u64,
+    progress: Option<mpsc::UnboundedSender<f64>>,
+) -> Result<(String, String), String> {
+    if delay_ms > 5000 {
+        return Err("delay_ms must be between 0 and 5000".to_string());
+    }
+    if let Some(tx) = progress {
+        let total_ms = delay_ms.max(1);
+        let tick = Duration::from_millis(total_ms.min(100));
+        tokio::spawn(async move {
+            let mut elapsed_ms = 0u64;
+            while elapsed_ms < total_ms {
+                tokio::time::sleep(tick).await;
+                elapsed_ms = (elapsed_ms + tick.as_millis() as u64).min(total_ms);
+                let _ = tx.send(elapsed_ms as f64 / total_ms as f64 * 100.0);
+            }
+        });
+    }
+    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    Ok((query, chrono::Utc::now().to_rfc3339()))
+}
+
+/// Fetches every endpoint concurrently, capped at `max_concurrency` in flight and bounded by
+/// `timeout_ms` per request. Sends each completed result on `progress` as it lands, then
+/// returns the full result set plus an aggregate summary.
+pub async fn run_batch_fetch(
+    client: reqwest::Client,
+    endpoints: Vec<String>,
+    max_concurrency: usize,
+    timeout_ms: u64,
+    progress: Option<mpsc::UnboundedSender<Value>>,
+) -> Result<Value, String> {
+    if max_concurrency == 0 {
+        return Err("max_concurrency must be greater than 0".to_string());
+    }
+    let start = Instant::now();
+    let semaphore = Arc::new(Semaphore::new(max_concurrency));
+    let timeout_dur = Duration::from_millis(timeout_ms);
+
+    let mut in_flight = FuturesUnordered::new();
+    for endpoint in endpoints {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        in_flight.push(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("semaphore should never be closed");
+            let request_start = Instant::now();
+            match tokio::time::timeout(timeout_dur, client.get(&endpoint).send()).await {
+                Ok(Ok(resp)) => serde_json::json!({
+                    "url": endpoint,
+                    "status_code": resp.status().as_u16(),
+                    "response_time_ms": request_start.elapsed().as_millis(),
+                }),
+                Ok(Err(e)) => serde_json::json!({
+                    "url": endpoint,
+                    "error": e.to_string(),
+                    "response_time_ms": request_start.elapsed().as_millis(),
+                }),
+                Err(_) => serde_json::json!({
+                    "url": endpoint,
+                    "timed_out": true,
+                    "response_time_ms": request_start.elapsed().as_millis(),
+                }),
+            }
+            // permit is released here as `_permit` drops
+        });
+    }
+
+    let mut results = Vec::new();
+    let mut successes = 0usize;
+    let mut failures = 0usize;
+    while let Some(result) = in_flight.next().await {
+        if result.get("status_code").is_some() {
+            successes += 1;
+        } else {
+            failures += 1;
+        }
+        if let Some(tx) = &progress {
+            let _ = tx.send(result.clone());
+        }
+        results.push(result);
+    }
+
+    Ok(serde_json::json!({
+        "results": results,
+        "summary": {
+            "total_requests": results.len(),
+            "successes": successes,
+            "failures": failures,
+            "elapsed_ms": start.elapsed().as_millis(),
+        },
+        "server_type": "rust"
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_pool() -> rayon::ThreadPool {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .expect("failed to build test compute pool")
+    }
+
+    #[tokio::test]
+    async fn iterative_allows_up_to_its_max() {
+        let pool = test_pool();
+        let (result, _) = run_fibonacci(&pool, ITERATIVE_FIBONACCI_MAX, true)
+            .await
+            .expect("n at the iterative max should succeed");
+        assert_eq!(result, fibonacci_iterative(ITERATIVE_FIBONACCI_MAX as u32));
+    }
+
+    #[tokio::test]
+    async fn recursive_rejects_n_above_its_max() {
+        let pool = test_pool();
+        let err = run_fibonacci(&pool, RECURSIVE_FIBONACCI_MAX + 1, false)
+            .await
+            .unwrap_err();
+        assert!(err.contains(&format!("between 0 and {RECURSIVE_FIBONACCI_MAX}")));
+    }
+
+    #[tokio::test]
+    async fn rejects_negative_n_in_either_mode() {
+        let pool = test_pool();
+        assert!(run_fibonacci(&pool, -1, false).await.is_err());
+        assert!(run_fibonacci(&pool, -1, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn recursive_and_iterative_modes_agree() {
+        let pool = test_pool();
+        let (recursive, _) = run_fibonacci(&pool, 10, false).await.unwrap();
+        let (iterative, _) = run_fibonacci(&pool, 10, true).await.unwrap();
+        assert_eq!(recursive, 55);
+        assert_eq!(recursive, iterative);
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_max_concurrency() {
+        let err = run_batch_fetch(reqwest::Client::new(), vec![], 0, 1000, None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("max_concurrency"));
+    }
+
+    #[tokio::test]
+    async fn reports_timeout_instead_of_hanging() {
+        // A listener that accepts but never writes a response, so the client-side timeout
+        // fires well before anything would reply.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                std::mem::forget(socket);
+            }
+        });
+
+        let endpoint = format!("http://{addr}/");
+        let response = run_batch_fetch(reqwest::Client::new(), vec![endpoint], 1, 50, None)
+            .await
+            .expect("a timed-out endpoint should still produce a result, not an Err");
+
+        assert_eq!(response["summary"]["failures"], 1);
+        assert_eq!(response["summary"]["successes"], 0);
+        assert_eq!(response["results"][0]["timed_out"], true);
+    }
+}